@@ -120,3 +120,65 @@ impl Chrometrace {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(name: &str) -> Frame {
+        Frame {
+            name: name.to_owned(),
+            filename: String::from("test.py"),
+            module: None,
+            short_filename: None,
+            line: 0,
+            locals: None,
+            is_entry: true,
+        }
+    }
+
+    fn trace(frames: Vec<Frame>) -> StackTrace {
+        StackTrace {
+            pid: 1,
+            thread_id: 1,
+            thread_name: None,
+            os_thread_id: None,
+            active: true,
+            owns_gil: false,
+            frames,
+            process_info: None,
+        }
+    }
+
+    #[test]
+    fn test_chrometrace_common_prefix() {
+        let mut chrometrace = Chrometrace::new(true);
+
+        // frames are stored leaf first, so 'a' is the root of both stacks
+        chrometrace
+            .increment(&trace(vec![frame("c"), frame("b"), frame("a")]))
+            .unwrap();
+        chrometrace
+            .increment(&trace(vec![frame("d"), frame("b"), frame("a")]))
+            .unwrap();
+
+        // the shared 'a;b' prefix stays open, and only the divergent leaf gets
+        // closed and reopened
+        let events: Vec<(&str, &str)> = chrometrace
+            .events
+            .iter()
+            .map(|e| (e.name.as_str(), e.ph.as_str()))
+            .collect();
+        assert_eq!(
+            events,
+            vec![("a", "B"), ("b", "B"), ("c", "B"), ("c", "E"), ("d", "B")]
+        );
+
+        // writing closes out every frame still open
+        let mut output = Vec::new();
+        chrometrace.write(&mut output).unwrap();
+        let written: Vec<serde_json::Value> = serde_json::from_slice(&output).unwrap();
+        assert_eq!(written.len(), 8);
+        assert!(written[5..].iter().all(|e| e["ph"] == "E"));
+    }
+}
@@ -44,6 +44,8 @@ pub struct Config {
     #[doc(hidden)]
     pub gil_only: bool,
     #[doc(hidden)]
+    pub thread_filter: Option<String>,
+    #[doc(hidden)]
     pub hide_progress: bool,
     #[doc(hidden)]
     pub capture_output: bool,
@@ -128,6 +130,7 @@ impl Default for Config {
             duration: RecordDuration::Unlimited,
             native: false,
             gil_only: false,
+            thread_filter: None,
             include_idle: false,
             include_thread_ids: false,
             hide_progress: false,
@@ -178,6 +181,7 @@ impl Config {
             .value_name("rate")
             .help("The number of samples to collect per second")
             .default_value("100")
+            .value_parser(clap::value_parser!(u64).range(1..))
             .takes_value(true);
 
         let subprocesses = Arg::new("subprocesses")
@@ -260,6 +264,18 @@ impl Config {
                     .long("threads")
                     .help("Show thread ids in the output"),
             )
+            .arg(
+                Arg::new("thread_filter")
+                    .long("thread-filter")
+                    .value_name("regex")
+                    .help(
+                        "Only include traces from threads whose name or id (as shown by --threads) \
+                         matches this regex. The regex can match anywhere in the name or id, use \
+                         ^ and $ to match exactly",
+                    )
+                    .value_parser(|s: &str| regex::Regex::new(s).map(|_| s.to_owned()))
+                    .takes_value(true),
+            )
             .arg(gil.clone())
             .arg(idle.clone())
             .arg(
@@ -363,7 +379,7 @@ impl Config {
 
         match subcommand {
             "record" => {
-                config.sampling_rate = matches.value_of_t("rate")?;
+                config.sampling_rate = *matches.get_one::<u64>("rate").unwrap();
                 config.duration = match matches.value_of("duration") {
                     Some("unlimited") | None => RecordDuration::Unlimited,
                     Some(seconds) => {
@@ -381,6 +397,7 @@ impl Config {
                     LineNo::LastInstruction
                 };
                 config.include_thread_ids = matches.occurrences_of("threads") > 0;
+                config.thread_filter = matches.get_one::<String>("thread_filter").cloned();
                 if matches.occurrences_of("nolineno") > 0 && matches.occurrences_of("function") > 0
                 {
                     eprintln!("--function & --nolinenos can't be used together");
@@ -389,7 +406,7 @@ impl Config {
                 config.hide_progress = matches.occurrences_of("hideprogress") > 0;
             }
             "top" => {
-                config.sampling_rate = matches.value_of_t("rate")?;
+                config.sampling_rate = *matches.get_one::<u64>("rate").unwrap();
                 config.refresh_seconds = *matches.get_one::<f64>("delay").unwrap();
             }
             "dump" => {
@@ -496,6 +513,7 @@ mod tests {
         assert_eq!(config.filename, Some(String::from("foo")));
         assert_eq!(config.format, Some(FileFormat::flamegraph));
         assert_eq!(config.command, String::from("record"));
+        assert_eq!(config.sampling_rate, 100);
 
         // same command using short versions of everything
         let short_config = get_config("py-spy r -p 1234 -o foo").unwrap();
@@ -527,11 +545,31 @@ mod tests {
         assert_eq!(config.include_idle, false);
         assert_eq!(config.gil_only, false);
         assert_eq!(config.include_thread_ids, false);
+        assert_eq!(config.thread_filter, None);
 
         let config_flags = get_config("py-spy r -p 1234 -o foo --idle --gil --threads").unwrap();
         assert_eq!(config_flags.include_idle, true);
         assert_eq!(config_flags.gil_only, true);
         assert_eq!(config_flags.include_thread_ids, true);
+
+        let config_filter = get_config("py-spy r -p 1234 -o foo --thread-filter ^worker").unwrap();
+        assert_eq!(config_filter.thread_filter, Some(String::from("^worker")));
+
+        // an invalid regex should fail to parse
+        assert_eq!(
+            get_config("py-spy r -p 1234 -o foo --thread-filter (")
+                .unwrap_err()
+                .kind,
+            clap::ErrorKind::ValueValidation
+        );
+
+        // a sampling rate of zero should be rejected rather than stalling the sampler
+        assert_eq!(
+            get_config("py-spy r -p 1234 -o foo --rate 0")
+                .unwrap_err()
+                .kind,
+            clap::ErrorKind::ValueValidation
+        );
     }
 
     #[test]
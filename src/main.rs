@@ -215,6 +215,13 @@ fn record_samples(pid: remoteprocess::Pid, config: &Config) -> Result<(), Error>
         }
     };
 
+    // the filter was already validated when parsing the arguments, Config just can't hold
+    // the compiled Regex since it derives PartialEq
+    let thread_filter = match &config.thread_filter {
+        Some(filter) => Some(regex::Regex::new(filter)?),
+        None => None,
+    };
+
     let mut errors = 0;
     let mut intervals = 0;
     let mut samples = 0;
@@ -270,6 +277,12 @@ fn record_samples(pid: remoteprocess::Pid, config: &Config) -> Result<(), Error>
                 continue;
             }
 
+            if let Some(filter) = &thread_filter {
+                if !trace.thread_matches(filter) {
+                    continue;
+                }
+            }
+
             if config.include_thread_ids {
                 let threadid = trace.format_threadid();
                 let thread_fmt = if let Some(thread_name) = &trace.thread_name {
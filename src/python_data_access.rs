@@ -24,22 +24,23 @@ pub fn copy_string<T: StringObject, P: ProcessMemory>(
 
     let bytes = process.copy(obj.address(ptr as usize), obj.size() * kind as usize)?;
 
+    // Strings read out of another process can be garbage (for instance if the object
+    // was freed or is being modified while we read it), so decode lossily and replace
+    // anything invalid with U+FFFD rather than failing or building an invalid char
     match (kind, obj.ascii()) {
-        (4, _) => {
-            #[allow(clippy::cast_ptr_alignment)]
-            let chars = unsafe {
-                std::slice::from_raw_parts(bytes.as_ptr() as *const char, bytes.len() / 4)
-            };
-            Ok(chars.iter().collect())
-        }
+        (4, _) => Ok(bytes
+            .chunks_exact(4)
+            .map(|c| u32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+            .map(|c| char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()),
         (2, _) => {
-            #[allow(clippy::cast_ptr_alignment)]
-            let chars = unsafe {
-                std::slice::from_raw_parts(bytes.as_ptr() as *const u16, bytes.len() / 2)
-            };
-            Ok(String::from_utf16(chars)?)
+            let chars: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+                .collect();
+            Ok(String::from_utf16_lossy(&chars))
         }
-        (1, true) => Ok(String::from_utf8(bytes)?),
+        (1, true) => Ok(String::from_utf8_lossy(&bytes).into_owned()),
         (1, false) => Ok(bytes.iter().map(|&b| b as char).collect()),
         _ => Err(format_err!("Unknown string kind {}", kind)),
     }
@@ -411,7 +412,7 @@ pub mod tests {
     // and then test out that the above code handles appropriately
     use super::*;
     use crate::python_bindings::v3_7_0::{
-        PyASCIIObject, PyBytesObject, PyUnicodeObject, PyVarObject,
+        PyASCIIObject, PyBytesObject, PyCompactUnicodeObject, PyUnicodeObject, PyVarObject,
     };
     use remoteprocess::LocalProcess;
     use std::ptr::copy_nonoverlapping;
@@ -476,6 +477,32 @@ pub mod tests {
         ret
     }
 
+    // non-ascii compact strings store their data after a PyCompactUnicodeObject header,
+    // so this writes the code units at that offset inside the storage buffer
+    pub fn to_compactobject(kind: u32, data: &[u8]) -> AllocatedPyASCIIObject {
+        let mut base = PyASCIIObject {
+            length: (data.len() / kind as usize) as isize,
+            ..Default::default()
+        };
+        base.state.set_compact(1);
+        base.state.set_kind(kind);
+        base.state.set_ascii(0);
+        let mut ret = AllocatedPyASCIIObject {
+            base,
+            storage: [0 as u8; 4096],
+        };
+        let offset =
+            std::mem::size_of::<PyCompactUnicodeObject>() - std::mem::size_of::<PyASCIIObject>();
+        ret.storage[offset..offset + data.len()].copy_from_slice(data);
+        ret
+    }
+
+    fn copy_compact_string(kind: u32, data: &[u8]) -> String {
+        let obj = to_compactobject(kind, data);
+        let unicode: &PyUnicodeObject = unsafe { std::mem::transmute(&obj.base) };
+        copy_string(unicode, &LocalProcess).unwrap()
+    }
+
     #[test]
     fn test_copy_string() {
         let original = "function_name";
@@ -486,6 +513,50 @@ pub mod tests {
         assert_eq!(copied, original);
     }
 
+    #[test]
+    fn test_copy_string_invalid_utf8() {
+        let mut obj = to_asciiobject("function_name");
+        obj.storage[0] = 0xff;
+
+        let unicode: &PyUnicodeObject = unsafe { std::mem::transmute(&obj.base) };
+        let copied = copy_string(unicode, &LocalProcess).unwrap();
+        assert_eq!(copied, "\u{FFFD}unction_name");
+    }
+
+    #[test]
+    fn test_copy_string_ucs2() {
+        let original = "héllo";
+        let data: Vec<u8> = original
+            .encode_utf16()
+            .flat_map(|c| c.to_ne_bytes())
+            .collect();
+        assert_eq!(copy_compact_string(2, &data), original);
+
+        // an unpaired surrogate isn't valid, and should be replaced
+        let data: Vec<u8> = [0xD800_u16, 'a' as u16]
+            .iter()
+            .flat_map(|c| c.to_ne_bytes())
+            .collect();
+        assert_eq!(copy_compact_string(2, &data), "\u{FFFD}a");
+    }
+
+    #[test]
+    fn test_copy_string_ucs4() {
+        let original = "é€😀";
+        let data: Vec<u8> = original
+            .chars()
+            .flat_map(|c| (c as u32).to_ne_bytes())
+            .collect();
+        assert_eq!(copy_compact_string(4, &data), original);
+
+        // surrogates aren't valid chars, and should be replaced
+        let data: Vec<u8> = [0xD800_u32, 'a' as u32]
+            .iter()
+            .flat_map(|c| c.to_ne_bytes())
+            .collect();
+        assert_eq!(copy_compact_string(4, &data), "\u{FFFD}a");
+    }
+
     #[test]
     fn test_copy_bytes() {
         let original = [10_u8, 20, 30, 40, 50, 70, 80];
@@ -30,6 +30,11 @@ pub struct Sample {
 
 impl Sampler {
     pub fn new(pid: Pid, config: &Config) -> Result<Sampler, Error> {
+        // the sampling thread would sleep forever between samples with a zero rate
+        if config.sampling_rate == 0 {
+            return Err(format_err!("Sampling rate must be greater than zero"));
+        }
+
         if config.subprocesses {
             Self::new_subprocess_sampler(pid, config)
         } else {
@@ -415,3 +420,24 @@ fn get_process_info(pid: Pid, spies: &HashMap<Pid, PythonSpyThread>) -> Option<B
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_sampling_rate() {
+        let config = Config {
+            sampling_rate: 0,
+            ..Default::default()
+        };
+        let pid = std::process::id() as Pid;
+        assert!(Sampler::new(pid, &config).is_err());
+
+        let config = Config {
+            subprocesses: true,
+            ..config
+        };
+        assert!(Sampler::new(pid, &config).is_err());
+    }
+}
@@ -211,6 +211,16 @@ impl StackTrace {
             None => format!("{:#X}", self.thread_id),
         }
     }
+
+    /// Whether the thread name or the thread id (as formatted by format_threadid) matches
+    /// the filter. This is a regex search, so the pattern can match anywhere in either string
+    pub fn thread_matches(&self, filter: &regex::Regex) -> bool {
+        let name_matches = self
+            .thread_name
+            .as_deref()
+            .is_some_and(|name| filter.is_match(name));
+        name_matches || filter.is_match(&self.format_threadid())
+    }
 }
 
 /// Returns the line number from a PyCodeObject (given the lasti index from a PyFrameObject)
@@ -293,8 +303,22 @@ mod tests {
     use super::*;
     use crate::python_bindings::v3_7_0::PyCodeObject;
     use crate::python_data_access::tests::to_byteobject;
+    use regex::Regex;
     use remoteprocess::LocalProcess;
 
+    fn thread_trace(thread_name: Option<&str>) -> StackTrace {
+        StackTrace {
+            pid: 1,
+            thread_id: 0x1234,
+            thread_name: thread_name.map(|name| name.to_owned()),
+            os_thread_id: Some(1234),
+            active: true,
+            owns_gil: false,
+            frames: Vec::new(),
+            process_info: None,
+        }
+    }
+
     #[test]
     fn test_get_line_number() {
         let mut lnotab = to_byteobject(&[0u8, 1, 10, 1, 8, 1, 4, 1]);
@@ -306,4 +330,26 @@ mod tests {
         let lineno = get_line_number(&code, 30, &LocalProcess).unwrap();
         assert_eq!(lineno, 7);
     }
+
+    #[test]
+    fn test_thread_matches() {
+        let trace = thread_trace(Some("worker-1"));
+        let id = regex::escape(&trace.format_threadid());
+        let id_filter = Regex::new(&format!("^{}$", id)).unwrap();
+
+        // the name matches
+        assert!(trace.thread_matches(&Regex::new("^worker").unwrap()));
+
+        // only the id matches
+        assert!(trace.thread_matches(&id_filter));
+
+        // neither matches. an anchored pattern doesn't match a longer id
+        assert!(!trace.thread_matches(&Regex::new("^main$").unwrap()));
+        assert!(!trace.thread_matches(&Regex::new("^12$").unwrap()));
+
+        // unnamed threads can only match on their id
+        let unnamed = thread_trace(None);
+        assert!(!unnamed.thread_matches(&Regex::new("^worker").unwrap()));
+        assert!(unnamed.thread_matches(&id_filter));
+    }
 }